@@ -0,0 +1,211 @@
+//! Rebinding layer mapping raw keys/mouse buttons to application-defined actions and axes.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use winit::event::{ModifiersState, MouseButton, VirtualKeyCode};
+
+use crate::Input;
+
+/// A single raw input that can trigger an action or one direction of an axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Trigger {
+    /// A keyboard key.
+    Key(VirtualKeyCode),
+    /// A mouse button.
+    Mouse(MouseButton),
+}
+
+/// A trigger paired with the modifiers that must be held for it to count.
+#[derive(Clone, Copy, Debug)]
+pub struct Binding {
+    /// The raw input that must be down.
+    pub trigger: Trigger,
+    /// Modifiers required alongside `trigger`, if any.
+    pub modifiers: Option<ModifiersState>,
+}
+
+impl Binding {
+    /// Create a binding with no modifier requirement.
+    pub fn new(trigger: Trigger) -> Self {
+        Binding {
+            trigger,
+            modifiers: None,
+        }
+    }
+
+    /// Require `modifiers` to be held for this binding to be considered down.
+    pub fn with_modifiers(mut self, modifiers: ModifiersState) -> Self {
+        self.modifiers = Some(modifiers);
+        self
+    }
+
+    fn is_down(&self, input: &Input) -> bool {
+        let (down, actual_modifiers) = match self.trigger {
+            Trigger::Key(key) => (input.is_key_down(key), input.key_modifiers_state(key)),
+            Trigger::Mouse(button) => (
+                input.is_mouse_button_down(button),
+                input.mouse_button_modifiers_state(button),
+            ),
+        };
+        down && self.modifiers.map_or(true, |m| m == actual_modifiers)
+    }
+
+    fn is_toggled_down(&self, input: &Input) -> bool {
+        let toggled_down = match self.trigger {
+            Trigger::Key(key) => input.is_key_toggled_down(key),
+            Trigger::Mouse(button) => input.is_mouse_button_toggled_down(button),
+        };
+        toggled_down && self.is_down(input)
+    }
+}
+
+/// A mouse-wheel axis an [`AxisBindings`] can draw an analog value from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollAxis {
+    /// `Input::get_mouse_wheel_horizontal`.
+    Horizontal,
+    /// `Input::get_mouse_wheel`.
+    Vertical,
+}
+
+/// The pair of bindings that drive one 1-D axis, positive and negative, plus an optional
+/// scroll-wheel source added on top of them for analog input.
+#[derive(Clone, Copy, Debug)]
+pub struct AxisBindings {
+    /// Trigger that should push the axis towards `1.0`.
+    pub positive: Binding,
+    /// Trigger that should push the axis towards `-1.0`.
+    pub negative: Binding,
+    /// Scroll wheel axis added to the digital `positive`/`negative` value, if any.
+    pub scroll: Option<ScrollAxis>,
+}
+
+/// Maps action ids (`A`) and axis ids (`X`) to the raw triggers that drive them.
+///
+/// `A` and `X` are typically small enums defined by the application, e.g. `enum Action { Jump }`.
+#[derive(Clone, Debug, Default)]
+pub struct Bindings<A: Copy + Eq + Hash, X: Copy + Eq + Hash> {
+    actions: HashMap<A, Vec<Binding>>,
+    axes: HashMap<X, AxisBindings>,
+}
+
+impl<A: Copy + Eq + Hash, X: Copy + Eq + Hash> Bindings<A, X> {
+    /// Create an empty set of bindings.
+    pub fn new() -> Self {
+        Bindings {
+            actions: HashMap::new(),
+            axes: HashMap::new(),
+        }
+    }
+
+    /// Bind `binding` as (one of) the inputs that activate `action`.
+    pub fn insert_action_binding(&mut self, action: A, binding: Binding) -> &mut Self {
+        self.actions.entry(action).or_insert_with(Vec::new).push(binding);
+        self
+    }
+
+    /// Bind the positive/negative triggers that drive `axis`.
+    pub fn insert_axis_binding(&mut self, axis: X, bindings: AxisBindings) -> &mut Self {
+        self.axes.insert(axis, bindings);
+        self
+    }
+
+    /// Check if `action` is currently down in `input` through any of its bound triggers.
+    pub fn is_action_down(&self, input: &Input, action: &A) -> bool {
+        self.actions
+            .get(action)
+            .map_or(false, |bindings| bindings.iter().any(|b| b.is_down(input)))
+    }
+
+    /// Check if `action` was toggled down this frame in `input` through any of its bound triggers.
+    pub fn is_action_toggled_down(&self, input: &Input, action: &A) -> bool {
+        self.actions.get(action).map_or(false, |bindings| {
+            bindings.iter().any(|b| b.is_toggled_down(input))
+        })
+    }
+
+    /// Resolve `axis` against `input`: the digital `positive`/`negative` triggers contribute
+    /// `1.0`/`-1.0`, and its `scroll` source, if any, adds the corresponding wheel delta on top.
+    pub fn axis_value(&self, input: &Input, axis: &X) -> f32 {
+        self.axes.get(axis).map_or(0.0, |bindings| {
+            let digital = bindings.positive.is_down(input) as i32 as f32
+                - bindings.negative.is_down(input) as i32 as f32;
+            let scroll = match bindings.scroll {
+                Some(ScrollAxis::Horizontal) => input.get_mouse_wheel_horizontal(),
+                Some(ScrollAxis::Vertical) => input.get_mouse_wheel(),
+                None => 0.0,
+            };
+            digital + scroll
+        })
+    }
+}
+
+/// An [`Input`] paired with the [`Bindings`] used to resolve it, so call sites can query
+/// application-level actions and axes instead of raw keys and buttons.
+#[derive(Clone, Debug, Default)]
+pub struct BoundInput<A: Copy + Eq + Hash, X: Copy + Eq + Hash> {
+    /// The underlying raw input state.
+    pub input: Input,
+    /// The bindings used to resolve actions and axes against `input`.
+    pub bindings: Bindings<A, X>,
+}
+
+impl<A: Copy + Eq + Hash, X: Copy + Eq + Hash> BoundInput<A, X> {
+    /// Check if `action` is currently down through any of its bound triggers.
+    pub fn action_down(&self, action: A) -> bool {
+        self.bindings.is_action_down(&self.input, &action)
+    }
+
+    /// Check if `action` was toggled down this frame through any of its bound triggers.
+    pub fn action_toggled_down(&self, action: A) -> bool {
+        self.bindings.is_action_toggled_down(&self.input, &action)
+    }
+
+    /// Resolve `axis`: its bound triggers contribute `1.0`/`-1.0`, plus its scroll wheel delta, if any.
+    pub fn axis_value(&self, axis: X) -> f32 {
+        self.bindings.axis_value(&self.input, &axis)
+    }
+}
+
+impl Input {
+    /// Pair this `Input` with `bindings`, yielding a [`BoundInput`] that can resolve
+    /// application-defined actions and axes instead of raw keys and buttons.
+    pub fn with_bindings<A: Copy + Eq + Hash, X: Copy + Eq + Hash>(
+        self,
+        bindings: Bindings<A, X>,
+    ) -> BoundInput<A, X> {
+        BoundInput {
+            input: self,
+            bindings,
+        }
+    }
+
+    /// Check if `action` is currently down through any of its triggers in `bindings`, without
+    /// pairing `self` with the bindings via [`Input::with_bindings`].
+    pub fn is_action_down<A: Copy + Eq + Hash, X: Copy + Eq + Hash>(
+        &self,
+        bindings: &Bindings<A, X>,
+        action: &A,
+    ) -> bool {
+        bindings.is_action_down(self, action)
+    }
+
+    /// Check if `action` was toggled down this frame through any of its triggers in `bindings`.
+    pub fn is_action_toggled_down<A: Copy + Eq + Hash, X: Copy + Eq + Hash>(
+        &self,
+        bindings: &Bindings<A, X>,
+        action: &A,
+    ) -> bool {
+        bindings.is_action_toggled_down(self, action)
+    }
+
+    /// Resolve `axis` against `bindings`: its bound triggers contribute `1.0`/`-1.0`, plus its
+    /// scroll wheel delta, if any.
+    pub fn axis_value<A: Copy + Eq + Hash, X: Copy + Eq + Hash>(
+        &self,
+        bindings: &Bindings<A, X>,
+        axis: &X,
+    ) -> f32 {
+        bindings.axis_value(self, axis)
+    }
+}
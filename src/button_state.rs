@@ -0,0 +1,94 @@
+//! Generic press/release tracking for any `Copy + Eq + Hash` button type, shared internally by
+//! the keyboard, mouse button and controller button stores. Backed by hash sets rather than a fixed-size array, so it has no upper bound on
+//! the values of `T` it can track: sparse key codes and `MouseButton::Other(n)` for large `n` are
+//! handled the same as any other value.
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use winit::event::ModifiersState;
+
+#[derive(Clone, Debug)]
+pub(crate) struct ButtonState<T: Copy + Eq + Hash> {
+    pressed: HashSet<T>,
+    just_pressed: HashSet<T>,
+    just_released: HashSet<T>,
+    modifiers: HashMap<T, ModifiersState>,
+}
+
+impl<T: Copy + Eq + Hash> Default for ButtonState<T> {
+    fn default() -> Self {
+        ButtonState {
+            pressed: HashSet::new(),
+            just_pressed: HashSet::new(),
+            just_released: HashSet::new(),
+            modifiers: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash> ButtonState<T> {
+    pub(crate) fn press(&mut self, button: T, modifiers: ModifiersState) {
+        if self.pressed.insert(button) {
+            self.just_pressed.insert(button);
+            self.just_released.remove(&button);
+        }
+        self.modifiers.insert(button, modifiers);
+    }
+
+    pub(crate) fn release(&mut self, button: T, modifiers: ModifiersState) {
+        if self.pressed.remove(&button) {
+            self.just_released.insert(button);
+            self.just_pressed.remove(&button);
+        }
+        self.modifiers.insert(button, modifiers);
+    }
+
+    pub(crate) fn pressed(&self, button: T) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    pub(crate) fn just_pressed(&self, button: T) -> bool {
+        self.just_pressed.contains(&button)
+    }
+
+    pub(crate) fn just_released(&self, button: T) -> bool {
+        self.just_released.contains(&button)
+    }
+
+    /// Iterate over every button currently held down.
+    pub(crate) fn iter_pressed(&self) -> impl Iterator<Item = T> + '_ {
+        self.pressed.iter().copied()
+    }
+
+    /// Iterate over every button that was pressed this frame.
+    pub(crate) fn iter_just_pressed(&self) -> impl Iterator<Item = T> + '_ {
+        self.just_pressed.iter().copied()
+    }
+
+    /// Iterate over every button that was released this frame.
+    pub(crate) fn iter_just_released(&self) -> impl Iterator<Item = T> + '_ {
+        self.just_released.iter().copied()
+    }
+
+    pub(crate) fn modifiers(&self, button: T) -> ModifiersState {
+        self.modifiers
+            .get(&button)
+            .copied()
+            .unwrap_or_else(ModifiersState::empty)
+    }
+
+    /// Called once per frame: a button that was pressed or released this frame stops being
+    /// "just" pressed/released, without affecting whether it's currently held.
+    pub(crate) fn clear_just(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+
+    /// Release every currently pressed button, e.g. so keys don't get stuck down when the
+    /// window loses focus mid-press.
+    pub(crate) fn release_all(&mut self, modifiers: ModifiersState) {
+        for button in self.pressed.clone() {
+            self.release(button, modifiers);
+        }
+    }
+}
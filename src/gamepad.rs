@@ -0,0 +1,120 @@
+//! Gamepad/controller state tracking alongside keyboard and mouse.
+//!
+//! `winit` doesn't emit controller events itself, so these are fed in through a neutral
+//! `ControllerButton`/`ControllerAxis` pair, typically bridged from an external library.
+use std::collections::HashMap;
+
+use winit::event::ElementState;
+
+use crate::button_state::ButtonState;
+use crate::Input;
+
+/// A controller button.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ControllerButton {
+    /// Bottom face button (A/Cross).
+    South,
+    /// Right face button (B/Circle).
+    East,
+    /// Left face button (X/Square).
+    West,
+    /// Top face button (Y/Triangle).
+    North,
+    /// Left shoulder bumper.
+    LeftShoulder,
+    /// Right shoulder bumper.
+    RightShoulder,
+    /// Left stick click.
+    LeftStick,
+    /// Right stick click.
+    RightStick,
+    /// Start/options button.
+    Start,
+    /// Select/back button.
+    Select,
+    /// D-pad up.
+    DPadUp,
+    /// D-pad down.
+    DPadDown,
+    /// D-pad left.
+    DPadLeft,
+    /// D-pad right.
+    DPadRight,
+}
+
+/// A controller axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ControllerAxis {
+    /// Left stick, horizontal.
+    LeftStickX,
+    /// Left stick, vertical.
+    LeftStickY,
+    /// Right stick, horizontal.
+    RightStickX,
+    /// Right stick, vertical.
+    RightStickY,
+    /// Left trigger.
+    LeftTrigger,
+    /// Right trigger.
+    RightTrigger,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ControllerState {
+    buttons: ButtonState<ControllerButton>,
+    axes: HashMap<ControllerAxis, f32>,
+}
+
+/// Per-connected-device controller state, keyed by device id.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Controllers {
+    devices: HashMap<u32, ControllerState>,
+}
+
+impl Input {
+    /// Register a controller button event for the device identified by `id`.
+    pub fn register_controller_button(&mut self, id: u32, button: ControllerButton, state: ElementState) {
+        let device = self.controllers.devices.entry(id).or_default();
+        match state {
+            ElementState::Pressed => device.buttons.press(button, self.current_modifiers),
+            ElementState::Released => device.buttons.release(button, self.current_modifiers),
+        }
+    }
+
+    /// Register a controller axis value for the device identified by `id`.
+    pub fn register_controller_axis(&mut self, id: u32, axis: ControllerAxis, value: f32) {
+        let device = self.controllers.devices.entry(id).or_default();
+        device.axes.insert(axis, value);
+    }
+
+    /// Check if `button` is currently down on controller `id`.
+    pub fn is_controller_button_down(&self, id: u32, button: ControllerButton) -> bool {
+        self.controllers
+            .devices
+            .get(&id)
+            .map_or(false, |device| device.buttons.pressed(button))
+    }
+
+    /// Check if `button` was toggled down this frame on controller `id`.
+    pub fn is_controller_button_toggled_down(&self, id: u32, button: ControllerButton) -> bool {
+        self.controllers
+            .devices
+            .get(&id)
+            .map_or(false, |device| device.buttons.just_pressed(button))
+    }
+
+    /// Get the last reported value of `axis` on controller `id`, or `0.0` if none was reported.
+    pub fn controller_axis_value(&self, id: u32, axis: ControllerAxis) -> f32 {
+        self.controllers
+            .devices
+            .get(&id)
+            .and_then(|device| device.axes.get(&axis).copied())
+            .unwrap_or(0.0)
+    }
+
+    pub(crate) fn clear_just_controller_buttons(&mut self) {
+        for device in self.controllers.devices.values_mut() {
+            device.buttons.clear_just();
+        }
+    }
+}
@@ -30,85 +30,52 @@
     unused_import_braces,
     unused_qualifications
 )]
-use std::fmt;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use winit::event::*;
 
 #[cfg(test)]
 mod test;
 
-const NUM_KEYS: usize = 163;
-const NUM_MOUSE_BUTTONS: usize = 256 + 3;
+mod bindings;
+pub use bindings::{AxisBindings, Binding, Bindings, BoundInput, ScrollAxis, Trigger};
 
-// ---
-
-#[derive(Clone)]
-struct Keys([KeyInput; NUM_KEYS]);
-
-impl fmt::Debug for Keys {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for idx in 0..self.0.len() - 1 {
-            write!(f, "{:?}", self.0[idx])?;
-        }
-        write!(f, "{:?}", self.0.last())
-    }
-}
-
-impl Default for Keys {
-    fn default() -> Self {
-        let default = KeyInput {
-            state: ElementState::Released,
-            modifiers: ModifiersState::empty(),
-        };
-        Keys([default; NUM_KEYS])
-    }
-}
-
-// ---
+mod button_state;
+use button_state::ButtonState;
 
-#[derive(Clone)]
-struct MouseButtons([MouseInput; NUM_MOUSE_BUTTONS]);
+mod synth;
+pub use synth::{RawInputs, SyntheticInput};
 
-impl fmt::Debug for MouseButtons {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for idx in 0..self.0.len() - 1 {
-            write!(f, "{:?}", self.0[idx])?;
-        }
-        write!(f, "{:?}", self.0.last())
-    }
-}
-
-impl Default for MouseButtons {
-    fn default() -> Self {
-        let default = MouseInput {
-            state: ElementState::Released,
-            modifiers: ModifiersState::empty(),
-        };
-        MouseButtons([default; NUM_MOUSE_BUTTONS])
-    }
-}
-
-// ---
+mod gamepad;
+pub use gamepad::{ControllerAxis, ControllerButton};
+use gamepad::Controllers;
 
 /// Position of the mouse
 #[derive(Clone, Copy)]
 pub struct MousePosition(i32, i32);
 
-/// Keyboard input as a buttonstate and modifier state
-#[derive(Clone, Copy, Debug)]
-pub struct KeyInput {
-    /// Modifiers pressed while this event occurred
-    pub modifiers: ModifiersState,
-    /// State of the button
-    pub state: ElementState,
+// ---
+
+/// Within this many pixels of the previous press, a new press counts towards the same click
+/// chain rather than starting a new one.
+const MULTI_CLICK_PIXEL_TOLERANCE: f32 = 4.0;
+
+/// Distinguishes notched mouse-wheel scrolling from pixel-precise trackpad scrolling, mirroring
+/// the two variants of [`MouseScrollDelta`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MouseScrollUnit {
+    /// The scroll delta is in lines/notches, as reported by a traditional mouse wheel.
+    Line,
+    /// The scroll delta is in pixels, as reported by a trackpad or precision touchpad.
+    Pixel,
 }
 
-/// Mouse input as a buttonstate and a modifier state
 #[derive(Clone, Copy, Debug)]
-pub struct MouseInput {
-    /// State of the button
-    pub state: ElementState,
-    /// Modifiers pressed while this event occurred
-    pub modifiers: ModifiersState,
+struct ClickState {
+    last_press: Instant,
+    last_position: (f32, f32),
+    count: u32,
+    fresh: bool,
 }
 
 // ---
@@ -118,35 +85,73 @@ pub struct MouseInput {
 /// This struct accumulates input events and allows them to be used throughout the program. Its
 /// main purpose is to resolve issues of multiple keypresses per-frame as well as accumulating
 /// mouse events such as position and mousewheel events.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct Input {
-    keys_now: Keys,
-    keys_before: Keys,
-
-    mouse_buttons_now: MouseButtons,
-    mouse_buttons_before: MouseButtons,
+    keys: ButtonState<VirtualKeyCode>,
+    mouse_buttons: ButtonState<MouseButton>,
 
     mouse_now: (f32, f32),
     mouse_before: (f32, f32),
 
-    mouse_wheel: f32,
+    mouse_wheel_line: (f32, f32),
+    mouse_wheel_pixels: (f32, f32),
+    last_scroll_unit: Option<MouseScrollUnit>,
+
+    mouse_clicks: HashMap<MouseButton, ClickState>,
+    multi_click_threshold: Duration,
+
+    raw_mouse_delta: (f64, f64),
+    use_raw_mouse_motion: bool,
+
+    text_input: String,
+
+    controllers: Controllers,
 
     hide_mouse: bool,
     hide_keys: bool,
     current_modifiers: ModifiersState,
 }
 
+impl Default for Input {
+    fn default() -> Self {
+        Input {
+            keys: ButtonState::default(),
+            mouse_buttons: ButtonState::default(),
+            mouse_now: (0.0, 0.0),
+            mouse_before: (0.0, 0.0),
+            mouse_wheel_line: (0.0, 0.0),
+            mouse_wheel_pixels: (0.0, 0.0),
+            last_scroll_unit: None,
+            mouse_clicks: HashMap::new(),
+            multi_click_threshold: Duration::from_millis(300),
+            raw_mouse_delta: (0.0, 0.0),
+            use_raw_mouse_motion: false,
+            text_input: String::new(),
+            controllers: Controllers::default(),
+            hide_mouse: false,
+            hide_keys: false,
+            current_modifiers: ModifiersState::empty(),
+        }
+    }
+}
+
 impl Input {
     /// Clear delta-based inputs such as mouse-wheel, and overwrite the previous mouse position
     pub fn prepare_for_next_frame(&mut self) {
-        self.mouse_wheel = 0.0;
+        self.mouse_wheel_line = (0.0, 0.0);
+        self.mouse_wheel_pixels = (0.0, 0.0);
+        self.last_scroll_unit = None;
+        self.raw_mouse_delta = (0.0, 0.0);
+        self.text_input.clear();
+        self.clear_just_controller_buttons();
         self.mouse_before.0 = self.mouse_now.0;
         self.mouse_before.1 = self.mouse_now.1;
 
-        self.keys_before.0.copy_from_slice(&self.keys_now.0);
-        self.mouse_buttons_before
-            .0
-            .copy_from_slice(&self.mouse_buttons_now.0);
+        self.keys.clear_just();
+        self.mouse_buttons.clear_just();
+        for click in self.mouse_clicks.values_mut() {
+            click.fresh = false;
+        }
         self.hide_mouse = false;
         self.hide_keys = false;
     }
@@ -169,15 +174,64 @@ impl Input {
             Event::WindowEvent { event, .. } => {
                 self.handle_window_event(event);
             }
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta },
+                ..
+            } => {
+                self.register_raw_mouse_motion(*delta);
+            }
             _ => {}
         }
     }
 
+    /// Register a raw relative mouse motion delta, as reported by `DeviceEvent::MouseMotion`.
+    pub fn register_raw_mouse_motion(&mut self, delta: (f64, f64)) {
+        self.raw_mouse_delta.0 += delta.0;
+        self.raw_mouse_delta.1 += delta.1;
+    }
+
+    /// Choose whether [`Input::get_mouse_moved`] reports window-clamped movement derived from
+    /// `CursorMoved` (the default), or raw unclamped device motion accumulated from
+    /// `DeviceEvent::MouseMotion`. Raw motion is what you want while the cursor is grabbed, e.g.
+    /// for an FPS-style camera, since absolute cursor position stops updating at the window
+    /// edges.
+    pub fn set_raw_mouse_motion(&mut self, enabled: bool) {
+        self.use_raw_mouse_motion = enabled;
+    }
+
+    /// Get the raw, unbounded relative mouse motion accumulated this frame from
+    /// `DeviceEvent::MouseMotion`, regardless of [`Input::set_raw_mouse_motion`].
+    pub fn get_raw_mouse_delta(&self) -> (f64, f64) {
+        self.raw_mouse_delta
+    }
+
+    /// Like [`Input::get_raw_mouse_delta`], narrowed to `f32`.
+    pub fn get_raw_mouse_moved(&self) -> (f32, f32) {
+        (self.raw_mouse_delta.0 as f32, self.raw_mouse_delta.1 as f32)
+    }
+
     /// Set the current modifier state.
     pub fn set_modifiers(&mut self, modifiers: ModifiersState) {
         self.current_modifiers = modifiers;
     }
 
+    pub(crate) fn current_modifiers(&self) -> ModifiersState {
+        self.current_modifiers
+    }
+
+    /// Accumulate a character received from `WindowEvent::ReceivedCharacter`, filtering out
+    /// control characters. Called automatically by [`Input::register_event`].
+    pub fn register_received_character(&mut self, c: char) {
+        if !c.is_control() {
+            self.text_input.push(c);
+        }
+    }
+
+    /// Get the text typed this frame, in order, as accumulated from `ReceivedCharacter` events.
+    pub fn get_text_input(&self) -> &str {
+        &self.text_input
+    }
+
     fn handle_window_event<'a>(&mut self, event: &WindowEvent<'a>) {
         match event {
             WindowEvent::KeyboardInput { input, .. } => {
@@ -195,102 +249,225 @@ impl Input {
             WindowEvent::ModifiersChanged(modifiers) => {
                 self.current_modifiers = *modifiers;
             }
+            WindowEvent::ReceivedCharacter(c) => {
+                self.register_received_character(*c);
+            }
+            WindowEvent::Focused(false) => {
+                self.release_all();
+            }
             _ => {}
         }
     }
 
+    /// Release every currently held key and mouse button, as happens when the window loses
+    /// focus mid-press and the real release event is never delivered.
+    fn release_all(&mut self) {
+        self.keys.release_all(self.current_modifiers);
+        self.mouse_buttons.release_all(self.current_modifiers);
+    }
+
     /// Register a keyboard input
     pub fn register_key(&mut self, input: &KeyboardInput) {
         if let KeyboardInput {
             virtual_keycode: Some(keycode),
+            state,
             ..
         } = input
         {
-            let keycode = *keycode as usize;
-            self.keys_before.0[keycode] = self.keys_now.0[keycode];
-            self.keys_now.0[keycode] = KeyInput {
-                state: input.state,
-                modifiers: self.current_modifiers,
-            };
+            match state {
+                ElementState::Pressed => self.keys.press(*keycode, self.current_modifiers),
+                ElementState::Released => self.keys.release(*keycode, self.current_modifiers),
+            }
         }
     }
 
     /// Check if a key is pressed
     pub fn is_key_down(&self, keycode: VirtualKeyCode) -> bool {
-        !self.hide_keys && self.keys_now.0[keycode as usize].state == ElementState::Pressed
+        !self.hide_keys && self.keys.pressed(keycode)
     }
 
     /// Check if a key is up (released)
     pub fn is_key_up(&self, keycode: VirtualKeyCode) -> bool {
-        self.hide_keys || self.keys_now.0[keycode as usize].state == ElementState::Released
+        self.hide_keys || !self.keys.pressed(keycode)
     }
 
     /// Check if a key has been toggled
     pub fn is_key_toggled(&self, keycode: VirtualKeyCode) -> bool {
-        !self.hide_keys
-            && self.keys_before.0[keycode as usize].state != self.keys_now.0[keycode as usize].state
+        !self.hide_keys && (self.keys.just_pressed(keycode) || self.keys.just_released(keycode))
     }
 
     /// Check if a key has been toggled and is pressed
     pub fn is_key_toggled_down(&self, keycode: VirtualKeyCode) -> bool {
-        self.is_key_down(keycode) && self.is_key_toggled(keycode)
+        !self.hide_keys && self.keys.just_pressed(keycode)
     }
 
     /// Check if a key has been toggled and is released
     pub fn is_key_toggled_up(&self, keycode: VirtualKeyCode) -> bool {
-        !self.is_key_down(keycode) && self.is_key_toggled(keycode)
+        !self.hide_keys && self.keys.just_released(keycode)
     }
 
     /// Get a key's modifiers state
     pub fn key_modifiers_state(&self, keycode: VirtualKeyCode) -> ModifiersState {
-        self.keys_now.0[keycode as usize].modifiers
+        self.keys.modifiers(keycode)
+    }
+
+    /// Iterate over every key currently held down.
+    pub fn pressed_keys(&self) -> impl Iterator<Item = VirtualKeyCode> + '_ {
+        self.keys.iter_pressed().filter(move |_| !self.hide_keys)
+    }
+
+    /// Iterate over every key toggled down this frame.
+    pub fn keys_toggled_down(&self) -> impl Iterator<Item = VirtualKeyCode> + '_ {
+        self.keys
+            .iter_just_pressed()
+            .filter(move |_| !self.hide_keys)
+    }
+
+    /// Iterate over every key toggled up this frame.
+    pub fn keys_toggled_up(&self) -> impl Iterator<Item = VirtualKeyCode> + '_ {
+        self.keys
+            .iter_just_released()
+            .filter(move |_| !self.hide_keys)
     }
 
     // ---
 
     /// Register a mouse button event
     pub fn register_mouse_input(&mut self, state: &ElementState, button: &MouseButton) {
-        let index = mouse_button_to_index(*button);
-        self.mouse_buttons_before.0[index] = self.mouse_buttons_now.0[index];
-        self.mouse_buttons_now.0[index] = MouseInput {
-            state: *state,
-            modifiers: self.current_modifiers,
+        self.register_mouse_input_at(state, button, Instant::now());
+    }
+
+    /// Register a mouse button event that happened at `now`.
+    ///
+    /// This is what drives multi-click detection (see [`Input::mouse_click_count`]); it exists
+    /// as a separate entry point so tests can feed explicit timestamps instead of relying on the
+    /// wall clock.
+    pub fn register_mouse_input_at(
+        &mut self,
+        state: &ElementState,
+        button: &MouseButton,
+        now: Instant,
+    ) {
+        if *state == ElementState::Pressed {
+            self.track_click(*button, now);
+        }
+        match state {
+            ElementState::Pressed => self.mouse_buttons.press(*button, self.current_modifiers),
+            ElementState::Released => self.mouse_buttons.release(*button, self.current_modifiers),
+        }
+    }
+
+    fn track_click(&mut self, button: MouseButton, now: Instant) {
+        let position = self.mouse_now;
+        let threshold = self.multi_click_threshold;
+        let click = self.mouse_clicks.entry(button).or_insert(ClickState {
+            last_press: now,
+            last_position: position,
+            count: 0,
+            fresh: false,
+        });
+
+        let dx = position.0 - click.last_position.0;
+        let dy = position.1 - click.last_position.1;
+        let continues_chain = now.saturating_duration_since(click.last_press) <= threshold
+            && (dx * dx + dy * dy).sqrt() <= MULTI_CLICK_PIXEL_TOLERANCE;
+
+        click.count = if continues_chain {
+            click.count % 3 + 1
+        } else {
+            1
         };
+        click.last_press = now;
+        click.last_position = position;
+        click.fresh = true;
+    }
+
+    /// Set how close together in time two presses must be to count as part of the same
+    /// multi-click chain. Defaults to 300ms.
+    pub fn set_multi_click_threshold(&mut self, threshold: Duration) {
+        self.multi_click_threshold = threshold;
+    }
+
+    /// Number of consecutive clicks registered for `button`: 1 for a lone click, 2 for a double
+    /// click, 3 for a triple click, wrapping back to 1 on the next click in the chain.
+    pub fn mouse_click_count(&self, button: MouseButton) -> u32 {
+        self.mouse_clicks.get(&button).map_or(0, |click| click.count)
+    }
+
+    /// Check if `button` completed a double click this frame.
+    pub fn is_double_click(&self, button: MouseButton) -> bool {
+        self.mouse_clicks
+            .get(&button)
+            .map_or(false, |click| click.fresh && click.count == 2)
+    }
+
+    /// Check if `button` completed a triple click this frame.
+    pub fn is_triple_click(&self, button: MouseButton) -> bool {
+        self.mouse_clicks
+            .get(&button)
+            .map_or(false, |click| click.fresh && click.count == 3)
+    }
+
+    /// Alias for [`Input::is_double_click`].
+    pub fn is_mouse_button_double_clicked(&self, button: MouseButton) -> bool {
+        self.is_double_click(button)
+    }
+
+    /// Alias for [`Input::is_triple_click`].
+    pub fn is_mouse_button_triple_clicked(&self, button: MouseButton) -> bool {
+        self.is_triple_click(button)
     }
 
     /// Check if a mouse button is pressed
     pub fn is_mouse_button_down(&self, button: MouseButton) -> bool {
-        let index = mouse_button_to_index(button);
-        !self.hide_mouse && self.mouse_buttons_now.0[index].state == ElementState::Pressed
+        !self.hide_mouse && self.mouse_buttons.pressed(button)
     }
 
     /// Check if a mouse button is released (up)
     pub fn is_mouse_button_up(&self, button: MouseButton) -> bool {
-        let index = mouse_button_to_index(button);
-        self.hide_mouse || self.mouse_buttons_now.0[index].state == ElementState::Released
+        self.hide_mouse || !self.mouse_buttons.pressed(button)
     }
 
     /// Check if a mouse button is toggled
     pub fn is_mouse_button_toggled(&self, button: MouseButton) -> bool {
-        let index = mouse_button_to_index(button);
         !self.hide_mouse
-            && self.mouse_buttons_before.0[index].state != self.mouse_buttons_now.0[index].state
+            && (self.mouse_buttons.just_pressed(button) || self.mouse_buttons.just_released(button))
     }
 
     /// Check if a mouse button is toggled and is pressed
     pub fn is_mouse_button_toggled_down(&self, button: MouseButton) -> bool {
-        self.is_mouse_button_toggled(button) && self.is_mouse_button_down(button)
+        !self.hide_mouse && self.mouse_buttons.just_pressed(button)
     }
 
     /// Check if a mouse button is toggled and is released
     pub fn is_mouse_button_toggled_up(&self, button: MouseButton) -> bool {
-        self.is_mouse_button_toggled(button) && self.is_mouse_button_up(button)
+        !self.hide_mouse && self.mouse_buttons.just_released(button)
     }
 
     /// Get a mouse button's modifiers state
     pub fn mouse_button_modifiers_state(&self, button: MouseButton) -> ModifiersState {
-        let index = mouse_button_to_index(button);
-        self.mouse_buttons_now.0[index].modifiers
+        self.mouse_buttons.modifiers(button)
+    }
+
+    /// Iterate over every mouse button currently held down.
+    pub fn pressed_mouse_buttons(&self) -> impl Iterator<Item = MouseButton> + '_ {
+        self.mouse_buttons
+            .iter_pressed()
+            .filter(move |_| !self.hide_mouse)
+    }
+
+    /// Iterate over every mouse button toggled down this frame.
+    pub fn mouse_buttons_toggled_down(&self) -> impl Iterator<Item = MouseButton> + '_ {
+        self.mouse_buttons
+            .iter_just_pressed()
+            .filter(move |_| !self.hide_mouse)
+    }
+
+    /// Iterate over every mouse button toggled up this frame.
+    pub fn mouse_buttons_toggled_up(&self) -> impl Iterator<Item = MouseButton> + '_ {
+        self.mouse_buttons
+            .iter_just_released()
+            .filter(move |_| !self.hide_mouse)
     }
 
     // ---
@@ -304,10 +481,16 @@ impl Input {
     /// Register a scroll wheel event
     pub fn register_mouse_wheel(&mut self, delta: &MouseScrollDelta) {
         match delta {
-            MouseScrollDelta::LineDelta(_, y) => {
-                self.mouse_wheel += y;
+            MouseScrollDelta::LineDelta(x, y) => {
+                self.mouse_wheel_line.0 += x;
+                self.mouse_wheel_line.1 += y;
+                self.last_scroll_unit = Some(MouseScrollUnit::Line);
+            }
+            MouseScrollDelta::PixelDelta(position) => {
+                self.mouse_wheel_pixels.0 += position.x as f32;
+                self.mouse_wheel_pixels.1 += position.y as f32;
+                self.last_scroll_unit = Some(MouseScrollUnit::Pixel);
             }
-            _ => {}
         }
     }
 
@@ -316,29 +499,54 @@ impl Input {
         (self.mouse_now.0, self.mouse_now.1)
     }
 
-    /// Get the mouse movement since last frame
+    /// Get the mouse movement since last frame.
+    ///
+    /// Reports raw device motion when [`Input::set_raw_mouse_motion`] is enabled, and
+    /// window-clamped absolute movement otherwise.
     pub fn get_mouse_moved(&self) -> (f32, f32) {
-        (
-            (self.mouse_now.0 - self.mouse_before.0),
-            (self.mouse_now.1 - self.mouse_before.1),
-        )
+        if self.use_raw_mouse_motion {
+            (self.raw_mouse_delta.0 as f32, self.raw_mouse_delta.1 as f32)
+        } else {
+            (
+                (self.mouse_now.0 - self.mouse_before.0),
+                (self.mouse_now.1 - self.mouse_before.1),
+            )
+        }
     }
 
-    /// Get the current mouse wheel value
+    /// Get the current mouse wheel value (vertical line scroll)
     pub fn get_mouse_wheel(&self) -> f32 {
-        self.mouse_wheel
+        self.mouse_wheel_line.1
     }
 
-    // ---
-}
+    /// Get the current horizontal mouse wheel value (line scroll)
+    pub fn get_mouse_wheel_horizontal(&self) -> f32 {
+        self.mouse_wheel_line.0
+    }
+
+    /// Get the accumulated pixel-precise scroll delta, as reported by a trackpad, separately
+    /// from the notched line delta returned by [`Input::get_mouse_wheel`].
+    pub fn get_mouse_scroll_pixels(&self) -> (f32, f32) {
+        self.mouse_wheel_pixels
+    }
+
+    /// Get the unit of the most recent scroll event this frame, if any occurred.
+    pub fn get_mouse_scroll_unit(&self) -> Option<MouseScrollUnit> {
+        self.last_scroll_unit
+    }
 
-fn mouse_button_to_index(button: MouseButton) -> usize {
-    match button {
-        MouseButton::Left => 0,
-        MouseButton::Right => 1,
-        MouseButton::Middle => 2,
-        MouseButton::Other(value) => 3 + value as usize,
+    /// Get the combined `(horizontal, vertical)` scroll delta for whichever unit was last
+    /// reported this frame (see [`Input::get_mouse_scroll_unit`]): line scroll from
+    /// [`Input::get_mouse_wheel_horizontal`]/[`Input::get_mouse_wheel`], or pixel scroll from
+    /// [`Input::get_mouse_scroll_pixels`] on a trackpad that only ever emits `PixelDelta`.
+    pub fn get_scroll_delta(&self) -> (f32, f32) {
+        match self.last_scroll_unit {
+            Some(MouseScrollUnit::Pixel) => self.mouse_wheel_pixels,
+            _ => self.mouse_wheel_line,
+        }
     }
+
+    // ---
 }
 
 #[cfg(test)]
@@ -0,0 +1,134 @@
+//! Drive an [`Input`] without constructing `winit` events by hand, so headless tests and demos
+//! can script deterministic input sequences.
+use winit::event::{ElementState, KeyboardInput, MouseButton, MouseScrollDelta, VirtualKeyCode};
+
+use crate::Input;
+
+/// A single synthetic input event, routed through the same `register_*` path a real winit event
+/// would take.
+#[derive(Clone, Copy, Debug)]
+pub enum SyntheticInput {
+    /// Press a key.
+    PressKey(VirtualKeyCode),
+    /// Release a key.
+    ReleaseKey(VirtualKeyCode),
+    /// Press a mouse button.
+    PressMouse(MouseButton),
+    /// Release a mouse button.
+    ReleaseMouse(MouseButton),
+    /// Scroll by `(x, y)` lines.
+    Scroll(f32, f32),
+    /// Move the cursor to `(x, y)`.
+    MoveCursor(f32, f32),
+}
+
+/// A batch of [`SyntheticInput`]s applied to an [`Input`] in one call.
+#[derive(Clone, Debug, Default)]
+pub struct RawInputs(Vec<SyntheticInput>);
+
+impl RawInputs {
+    /// Start an empty batch.
+    pub fn new() -> Self {
+        RawInputs(Vec::new())
+    }
+
+    /// Queue a key press.
+    pub fn press_key(mut self, keycode: VirtualKeyCode) -> Self {
+        self.0.push(SyntheticInput::PressKey(keycode));
+        self
+    }
+
+    /// Queue a key release.
+    pub fn release_key(mut self, keycode: VirtualKeyCode) -> Self {
+        self.0.push(SyntheticInput::ReleaseKey(keycode));
+        self
+    }
+
+    /// Queue a mouse button press.
+    pub fn press_mouse(mut self, button: MouseButton) -> Self {
+        self.0.push(SyntheticInput::PressMouse(button));
+        self
+    }
+
+    /// Queue a mouse button release.
+    pub fn release_mouse(mut self, button: MouseButton) -> Self {
+        self.0.push(SyntheticInput::ReleaseMouse(button));
+        self
+    }
+
+    /// Queue a scroll by `(x, y)` lines.
+    pub fn scroll(mut self, x: f32, y: f32) -> Self {
+        self.0.push(SyntheticInput::Scroll(x, y));
+        self
+    }
+
+    /// Queue a cursor move to `(x, y)`.
+    pub fn move_cursor(mut self, x: f32, y: f32) -> Self {
+        self.0.push(SyntheticInput::MoveCursor(x, y));
+        self
+    }
+
+    /// Apply every queued input to `input`, in order.
+    pub fn apply(&self, input: &mut Input) {
+        for synthetic in &self.0 {
+            input.send_input(*synthetic);
+        }
+    }
+}
+
+impl Input {
+    /// Apply one synthetic input, routing through the same `register_*` path a real winit event
+    /// would take.
+    pub fn send_input(&mut self, synthetic: SyntheticInput) {
+        match synthetic {
+            SyntheticInput::PressKey(keycode) => self.press_key(keycode),
+            SyntheticInput::ReleaseKey(keycode) => self.release_key(keycode),
+            SyntheticInput::PressMouse(button) => self.press_mouse(button),
+            SyntheticInput::ReleaseMouse(button) => self.release_mouse(button),
+            SyntheticInput::Scroll(x, y) => self.scroll(x, y),
+            SyntheticInput::MoveCursor(x, y) => self.move_cursor(x, y),
+        }
+    }
+
+    /// Press `keycode`, as if synthesizing a `KeyboardInput` event.
+    pub fn press_key(&mut self, keycode: VirtualKeyCode) {
+        let modifiers = self.current_modifiers();
+        self.register_key(&KeyboardInput {
+            scancode: 0,
+            state: ElementState::Pressed,
+            virtual_keycode: Some(keycode),
+            modifiers,
+        });
+    }
+
+    /// Release `keycode`, as if synthesizing a `KeyboardInput` event.
+    pub fn release_key(&mut self, keycode: VirtualKeyCode) {
+        let modifiers = self.current_modifiers();
+        self.register_key(&KeyboardInput {
+            scancode: 0,
+            state: ElementState::Released,
+            virtual_keycode: Some(keycode),
+            modifiers,
+        });
+    }
+
+    /// Press `button`, as if synthesizing a `MouseInput` event.
+    pub fn press_mouse(&mut self, button: MouseButton) {
+        self.register_mouse_input(&ElementState::Pressed, &button);
+    }
+
+    /// Release `button`, as if synthesizing a `MouseInput` event.
+    pub fn release_mouse(&mut self, button: MouseButton) {
+        self.register_mouse_input(&ElementState::Released, &button);
+    }
+
+    /// Scroll by `(x, y)` lines, as if synthesizing a `MouseWheel` event.
+    pub fn scroll(&mut self, x: f32, y: f32) {
+        self.register_mouse_wheel(&MouseScrollDelta::LineDelta(x, y));
+    }
+
+    /// Move the cursor to `(x, y)`, as if synthesizing a `CursorMoved` event.
+    pub fn move_cursor(&mut self, x: f32, y: f32) {
+        self.register_mouse_position(x, y);
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use std::time::{Duration, Instant};
 
 #[test]
 fn tri_state_switch_pressed_released_pressed() {
@@ -30,6 +31,26 @@ fn tri_state_switch_pressed_released_pressed() {
     assert_eq!(true, input.is_key_down(VirtualKeyCode::A));
 }
 
+#[test]
+fn repeated_press_events_do_not_retoggle() {
+    let mut input = Input::default();
+    let key_event = KeyboardInput {
+        scancode: 0,
+        state: ElementState::Pressed,
+        virtual_keycode: Some(VirtualKeyCode::A),
+        modifiers: ModifiersState::default(),
+    };
+
+    input.register_key(&key_event);
+    assert!(input.is_key_toggled_down(VirtualKeyCode::A));
+
+    input.prepare_for_next_frame();
+    // OS key-repeat resends `Pressed` for a held key; it must not look like a fresh toggle.
+    input.register_key(&key_event);
+    assert!(input.is_key_down(VirtualKeyCode::A));
+    assert!(!input.is_key_toggled_down(VirtualKeyCode::A));
+}
+
 #[test]
 fn tri_state_switch_released_pressed_released() {
     let mut input = Input::default();
@@ -184,6 +205,47 @@ fn accumulate_mouse_wheel_deltas() {
     assert_eq!(0.0, input.get_mouse_wheel());
 }
 
+#[test]
+fn accumulate_horizontal_and_pixel_scroll() {
+    let mut input = Input::default();
+    input.register_mouse_wheel(&MouseScrollDelta::LineDelta(1.0, 0.0));
+    input.register_mouse_wheel(&MouseScrollDelta::LineDelta(2.0, 0.0));
+    assert_eq!(3.0, input.get_mouse_wheel_horizontal());
+    assert_eq!((3.0, 0.0), input.get_scroll_delta());
+    assert_eq!(Some(MouseScrollUnit::Line), input.get_mouse_scroll_unit());
+
+    input.register_mouse_wheel(&MouseScrollDelta::PixelDelta(
+        winit::dpi::PhysicalPosition::new(4.0, 5.0),
+    ));
+    assert_eq!((4.0, 5.0), input.get_mouse_scroll_pixels());
+    assert_eq!(Some(MouseScrollUnit::Pixel), input.get_mouse_scroll_unit());
+    assert_eq!((4.0, 5.0), input.get_scroll_delta());
+
+    input.prepare_for_next_frame();
+    assert_eq!(0.0, input.get_mouse_wheel_horizontal());
+    assert_eq!((0.0, 0.0), input.get_mouse_scroll_pixels());
+    assert_eq!(None, input.get_mouse_scroll_unit());
+}
+
+#[test]
+fn raw_mouse_motion_ignores_window_clamping() {
+    let mut input = Input::default();
+    input.register_mouse_position(5.0, 5.0);
+    input.prepare_for_next_frame();
+
+    input.register_raw_mouse_motion((12.0, -3.0));
+
+    assert_eq!((12.0, -3.0), input.get_raw_mouse_delta());
+    assert_eq!((12.0, -3.0), input.get_raw_mouse_moved());
+    assert_eq!((0.0, 0.0), input.get_mouse_moved());
+
+    input.set_raw_mouse_motion(true);
+    assert_eq!((12.0, -3.0), input.get_mouse_moved());
+
+    input.prepare_for_next_frame();
+    assert_eq!((0.0, 0.0), input.get_raw_mouse_delta());
+}
+
 #[test]
 fn ensure_boundaries_ok() {
     let mut input = Input::default();
@@ -240,3 +302,250 @@ fn hide_mouse_and_keys() {
     assert!(input.is_mouse_button_up(MouseButton::Left));
     assert!(!input.is_mouse_button_toggled(MouseButton::Left));
 }
+
+#[test]
+fn losing_focus_releases_stuck_keys_and_buttons() {
+    let mut input = Input::default();
+    input.register_key(&KeyboardInput {
+        scancode: 0,
+        state: ElementState::Pressed,
+        virtual_keycode: Some(VirtualKeyCode::W),
+        modifiers: ModifiersState::default(),
+    });
+    input.register_mouse_input(&ElementState::Pressed, &MouseButton::Left);
+    assert!(input.is_key_down(VirtualKeyCode::W));
+    assert!(input.is_mouse_button_down(MouseButton::Left));
+
+    input.handle_window_event(&WindowEvent::Focused(false));
+
+    assert!(!input.is_key_down(VirtualKeyCode::W));
+    assert!(!input.is_mouse_button_down(MouseButton::Left));
+    assert!(input.is_key_toggled_up(VirtualKeyCode::W));
+    assert!(input.is_mouse_button_toggled_up(MouseButton::Left));
+}
+
+#[test]
+fn double_and_triple_click_detection() {
+    let mut input = Input::default();
+    let t0 = Instant::now();
+
+    input.register_mouse_input_at(&ElementState::Pressed, &MouseButton::Left, t0);
+    input.register_mouse_input_at(&ElementState::Released, &MouseButton::Left, t0);
+    assert_eq!(1, input.mouse_click_count(MouseButton::Left));
+    assert!(!input.is_double_click(MouseButton::Left));
+
+    let t1 = t0 + Duration::from_millis(100);
+    input.register_mouse_input_at(&ElementState::Pressed, &MouseButton::Left, t1);
+    assert_eq!(2, input.mouse_click_count(MouseButton::Left));
+    assert!(input.is_double_click(MouseButton::Left));
+
+    let t2 = t1 + Duration::from_millis(100);
+    input.register_mouse_input_at(&ElementState::Pressed, &MouseButton::Left, t2);
+    assert_eq!(3, input.mouse_click_count(MouseButton::Left));
+    assert!(input.is_triple_click(MouseButton::Left));
+
+    let t3 = t2 + Duration::from_secs(1);
+    input.register_mouse_input_at(&ElementState::Pressed, &MouseButton::Left, t3);
+    assert_eq!(1, input.mouse_click_count(MouseButton::Left));
+}
+
+#[test]
+fn double_click_flag_is_cleared_next_frame() {
+    let mut input = Input::default();
+    let t0 = Instant::now();
+
+    input.register_mouse_input_at(&ElementState::Pressed, &MouseButton::Left, t0);
+    let t1 = t0 + Duration::from_millis(100);
+    input.register_mouse_input_at(&ElementState::Pressed, &MouseButton::Left, t1);
+    assert!(input.is_mouse_button_double_clicked(MouseButton::Left));
+
+    input.prepare_for_next_frame();
+    assert!(!input.is_double_click(MouseButton::Left));
+    assert_eq!(2, input.mouse_click_count(MouseButton::Left));
+}
+
+#[test]
+fn synthetic_input_without_winit_events() {
+    let mut input = Input::default();
+
+    input.press_key(VirtualKeyCode::A);
+    assert!(input.is_key_toggled_down(VirtualKeyCode::A));
+
+    input.release_key(VirtualKeyCode::A);
+    input.press_mouse(MouseButton::Left);
+    input.scroll(0.0, 1.0);
+    input.move_cursor(3.0, 4.0);
+
+    assert!(input.is_mouse_button_down(MouseButton::Left));
+    assert_eq!(1.0, input.get_mouse_wheel());
+    assert_eq!((3.0, 4.0), input.get_mouse_position());
+}
+
+#[test]
+fn raw_inputs_batch_applies_in_order() {
+    let mut input = Input::default();
+
+    RawInputs::new()
+        .press_key(VirtualKeyCode::W)
+        .move_cursor(1.0, 2.0)
+        .press_mouse(MouseButton::Right)
+        .apply(&mut input);
+
+    assert!(input.is_key_down(VirtualKeyCode::W));
+    assert_eq!((1.0, 2.0), input.get_mouse_position());
+    assert!(input.is_mouse_button_down(MouseButton::Right));
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Axis {
+    Horizontal,
+}
+
+#[test]
+fn action_and_axis_bindings_resolve_against_input() {
+    let mut input = Input::default();
+    let mut bindings = Bindings::new();
+    bindings.insert_action_binding(Action::Jump, Binding::new(Trigger::Key(VirtualKeyCode::Space)));
+    bindings.insert_axis_binding(
+        Axis::Horizontal,
+        AxisBindings {
+            positive: Binding::new(Trigger::Key(VirtualKeyCode::D)),
+            negative: Binding::new(Trigger::Key(VirtualKeyCode::A)),
+            scroll: None,
+        },
+    );
+
+    assert!(!input.is_action_down(&bindings, &Action::Jump));
+    assert_eq!(0.0, input.axis_value(&bindings, &Axis::Horizontal));
+
+    input.press_key(VirtualKeyCode::Space);
+    input.press_key(VirtualKeyCode::D);
+
+    assert!(input.is_action_down(&bindings, &Action::Jump));
+    assert!(input.is_action_toggled_down(&bindings, &Action::Jump));
+    assert_eq!(1.0, input.axis_value(&bindings, &Axis::Horizontal));
+
+    input.prepare_for_next_frame();
+    assert!(!input.is_action_toggled_down(&bindings, &Action::Jump));
+}
+
+#[test]
+fn scroll_backed_axis_adds_wheel_delta_to_digital_value() {
+    let mut input = Input::default();
+    let mut bindings = Bindings::new();
+    bindings.insert_axis_binding(
+        Axis::Horizontal,
+        AxisBindings {
+            positive: Binding::new(Trigger::Key(VirtualKeyCode::D)),
+            negative: Binding::new(Trigger::Key(VirtualKeyCode::A)),
+            scroll: Some(ScrollAxis::Vertical),
+        },
+    );
+
+    input.register_mouse_wheel(&MouseScrollDelta::LineDelta(0.0, 2.5));
+    assert_eq!(2.5, input.axis_value(&bindings, &Axis::Horizontal));
+
+    input.press_key(VirtualKeyCode::D);
+    assert_eq!(3.5, input.axis_value(&bindings, &Axis::Horizontal));
+}
+
+#[test]
+fn modifier_qualified_binding_only_fires_with_matching_modifiers() {
+    let mut input = Input::default();
+    let mut bindings = Bindings::new();
+    bindings.insert_action_binding(
+        Action::Jump,
+        Binding::new(Trigger::Key(VirtualKeyCode::Space)).with_modifiers(ModifiersState::CTRL),
+    );
+
+    input.register_key(&KeyboardInput {
+        scancode: 0,
+        state: ElementState::Pressed,
+        virtual_keycode: Some(VirtualKeyCode::Space),
+        modifiers: ModifiersState::empty(),
+    });
+    assert!(!input.is_action_down(&bindings, &Action::Jump));
+
+    input.register_key(&KeyboardInput {
+        scancode: 0,
+        state: ElementState::Released,
+        virtual_keycode: Some(VirtualKeyCode::Space),
+        modifiers: ModifiersState::empty(),
+    });
+    input.set_modifiers(ModifiersState::CTRL);
+    input.register_key(&KeyboardInput {
+        scancode: 0,
+        state: ElementState::Pressed,
+        virtual_keycode: Some(VirtualKeyCode::Space),
+        modifiers: ModifiersState::empty(),
+    });
+    assert!(input.is_action_down(&bindings, &Action::Jump));
+}
+
+#[test]
+fn accumulate_text_input_and_filter_control_chars() {
+    let mut input = Input::default();
+
+    input.register_received_character('h');
+    input.register_received_character('i');
+    input.register_received_character('\u{8}');
+    assert_eq!("hi", input.get_text_input());
+
+    input.prepare_for_next_frame();
+    assert_eq!("", input.get_text_input());
+}
+
+#[test]
+fn controller_buttons_and_axes_are_tracked_per_device() {
+    let mut input = Input::default();
+
+    input.register_controller_button(0, ControllerButton::South, ElementState::Pressed);
+    input.register_controller_axis(0, ControllerAxis::LeftStickX, 0.75);
+
+    assert!(input.is_controller_button_down(0, ControllerButton::South));
+    assert!(input.is_controller_button_toggled_down(0, ControllerButton::South));
+    assert_eq!(0.75, input.controller_axis_value(0, ControllerAxis::LeftStickX));
+
+    assert!(!input.is_controller_button_down(1, ControllerButton::South));
+    assert_eq!(0.0, input.controller_axis_value(1, ControllerAxis::LeftStickX));
+
+    input.prepare_for_next_frame();
+    assert!(!input.is_controller_button_toggled_down(0, ControllerButton::South));
+    assert!(input.is_controller_button_down(0, ControllerButton::South));
+
+    input.register_controller_button(0, ControllerButton::South, ElementState::Released);
+    assert!(!input.is_controller_button_down(0, ControllerButton::South));
+}
+
+#[test]
+fn iterate_pressed_and_toggled_keys_and_buttons() {
+    let mut input = Input::default();
+
+    input.press_key(VirtualKeyCode::W);
+    input.press_key(VirtualKeyCode::A);
+    input.register_mouse_input(&ElementState::Pressed, &MouseButton::Left);
+
+    let mut pressed: Vec<_> = input.pressed_keys().collect();
+    pressed.sort_by_key(|key| *key as u32);
+    assert_eq!(vec![VirtualKeyCode::A, VirtualKeyCode::W], pressed);
+
+    let mut toggled_down: Vec<_> = input.keys_toggled_down().collect();
+    toggled_down.sort_by_key(|key| *key as u32);
+    assert_eq!(vec![VirtualKeyCode::A, VirtualKeyCode::W], toggled_down);
+
+    assert_eq!(vec![MouseButton::Left], input.pressed_mouse_buttons().collect::<Vec<_>>());
+    assert_eq!(
+        vec![MouseButton::Left],
+        input.mouse_buttons_toggled_down().collect::<Vec<_>>()
+    );
+
+    input.prepare_for_next_frame();
+    assert_eq!(0, input.keys_toggled_down().count());
+    assert_eq!(0, input.mouse_buttons_toggled_down().count());
+    assert_eq!(2, input.pressed_keys().count());
+}